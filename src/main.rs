@@ -1,9 +1,15 @@
 use std::{env, fs, io};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use clap::Parser;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use regex::Regex;
 use fantoccini::{Client, Locator};
 use fantoccini::error::NewSessionError;
+use cookie::Expiration;
+use futures::future::join_all;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use tokio::time::{sleep, Duration};
 use thiserror::Error;
 
@@ -11,17 +17,35 @@ use thiserror::Error;
 #[derive(Parser)]
 #[command(author, version, about = "Fetch Zenn scrap and save as Markdown file")]
 struct Args {
-    /// Zenn scrap URL or slug (e.g. https://zenn.dev/xxx/scraps/your_slug)
-    url: String,
-    /// Output Markdown file path (defaults to `<slug>.md`)
+    /// One or more Zenn scrap URLs or slugs (e.g. https://zenn.dev/xxx/scraps/your_slug); not required with --logout
+    url: Vec<String>,
+    /// Read additional URLs/slugs to fetch from a file, one per line
+    #[arg(long)]
+    from_file: Option<String>,
+    /// Output Markdown file path (defaults to `<title>(<slug>).md`; ignored when fetching more than one scrap)
     #[arg(short, long)]
     output: Option<String>,
     /// Zenn session cookie, falls back to env ZENN_AUTH_COOKIE
     #[arg(long)]
     cookie: Option<String>,
+    /// Path to a Netscape/Mozilla `cookies.txt` jar, checked ahead of ZENN_AUTH_COOKIE
+    #[arg(long)]
+    cookie_file: Option<String>,
+    /// Path to the persisted session jar (defaults to a file under the OS config dir)
+    #[arg(long)]
+    session_store: Option<String>,
+    /// Delete the persisted session jar and exit
+    #[arg(long, alias = "clear-session")]
+    logout: bool,
     /// Skip rendering comment headers (author and timestamp)
     #[arg(long)]
     skip_header: bool,
+    /// Produce self-contained Markdown by downloading scrap images alongside it
+    #[arg(long)]
+    embed_assets: bool,
+    /// With --embed-assets, inline images as data: URIs instead of an assets directory
+    #[arg(long)]
+    inline: bool,
 }
 
 #[derive(Error, Debug)]
@@ -38,6 +62,8 @@ enum AppError {
     MissingEnv(String),
     #[error("Invalid scrap URL or slug")]
     BadSlug,
+    #[error("Invalid cookie jar entry: {0}")]
+    BadCookieJar(String),
 }
 
 #[derive(Deserialize)]
@@ -67,7 +93,168 @@ fn extract_slug(input: &str) -> Result<String, AppError> {
     }
 }
 
-async fn manual_login_cookie() -> Result<String, AppError> {
+/// A single entry from a Netscape/Mozilla `cookies.txt` jar.
+struct NetscapeCookie {
+    domain: String,
+    include_subdomains: bool,
+    path: String,
+    secure: bool,
+    /// Unix seconds; `0` means a session cookie that never expires.
+    expires: u64,
+    name: String,
+    value: String,
+}
+
+impl NetscapeCookie {
+    /// Whether this cookie's expiry has passed, relative to now. `0` never expires.
+    fn is_expired(&self) -> bool {
+        if self.expires == 0 {
+            return false;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.expires < now
+    }
+
+    /// Whether this cookie should be sent for `url`, per scheme/host/path rules.
+    fn matches_url(&self, url: &str) -> bool {
+        let (scheme, rest) = match url.split_once("://") {
+            Some(parts) => parts,
+            None => return false,
+        };
+        if self.secure && scheme != "https" {
+            return false;
+        }
+        let (host, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, "/"),
+        };
+        let host_matches = if self.include_subdomains {
+            host == self.domain || host.ends_with(&format!(".{}", self.domain))
+        } else {
+            host == self.domain
+        };
+        host_matches && path.starts_with(&self.path)
+    }
+}
+
+/// Parse a Netscape/Mozilla `cookies.txt` file into cookie entries.
+fn parse_cookie_jar(path: &str) -> Result<Vec<NetscapeCookie>, AppError> {
+    parse_cookie_jar_str(&fs::read_to_string(path)?)
+}
+
+/// Parse Netscape/Mozilla `cookies.txt` contents (one record per line) into cookie entries.
+fn parse_cookie_jar_str(contents: &str) -> Result<Vec<NetscapeCookie>, AppError> {
+    let mut cookies = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim_start_matches("#HttpOnly_");
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return Err(AppError::BadCookieJar(format!(
+                "expected 7 tab-separated fields, got {}: {}",
+                fields.len(),
+                line
+            )));
+        }
+        let expires: u64 = fields[4]
+            .parse()
+            .map_err(|_| AppError::BadCookieJar(format!("invalid expires field: {}", fields[4])))?;
+        cookies.push(NetscapeCookie {
+            // Netscape jars conventionally prefix the domain with a dot when it applies to
+            // subdomains; `include_subdomains` already carries that meaning, so normalize it away.
+            domain: fields[0].trim_start_matches('.').to_string(),
+            include_subdomains: fields[1].eq_ignore_ascii_case("TRUE"),
+            path: fields[2].to_string(),
+            secure: fields[3].eq_ignore_ascii_case("TRUE"),
+            expires,
+            name: fields[5].to_string(),
+            value: fields[6].to_string(),
+        });
+    }
+    Ok(cookies)
+}
+
+/// Build a `name=value; ...` COOKIE header from the jar entries that apply to `url`.
+fn cookie_header_for_url(cookies: &[NetscapeCookie], url: &str) -> String {
+    cookies
+        .iter()
+        .filter(|c| !c.is_expired() && c.matches_url(url))
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// A persisted `_zenn_session` cookie, stored as JSON between runs.
+#[derive(Serialize, Deserialize)]
+struct SessionJar {
+    name: String,
+    value: String,
+    /// Unix seconds; `None` means the browser didn't report an expiry (treated as never expiring).
+    expires: Option<u64>,
+}
+
+impl SessionJar {
+    fn is_expired(&self) -> bool {
+        match self.expires {
+            None => false,
+            Some(expires) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                expires < now
+            }
+        }
+    }
+
+    fn cookie_header(&self) -> String {
+        format!("{}={}", self.name, self.value)
+    }
+}
+
+/// Default location for the persisted session jar, under the OS config dir.
+fn default_session_store_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("zenn-scrap-to-md")
+        .join("session.json")
+}
+
+/// Load the session jar from `path`, discarding it if it has expired.
+fn load_session_jar(path: &PathBuf) -> Option<SessionJar> {
+    let contents = fs::read_to_string(path).ok()?;
+    let jar: SessionJar = serde_json::from_str(&contents).ok()?;
+    if jar.is_expired() {
+        None
+    } else {
+        Some(jar)
+    }
+}
+
+/// Persist the session jar to `path`, creating parent directories as needed.
+fn save_session_jar(path: &PathBuf, jar: &SessionJar) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(jar).unwrap())?;
+    Ok(())
+}
+
+/// Delete the persisted session jar, if present.
+fn clear_session_jar(path: &PathBuf) -> Result<(), AppError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(AppError::Io(e)),
+    }
+}
+
+async fn manual_login_cookie() -> Result<SessionJar, AppError> {
     // Load .env if exists (optional)
     dotenv::dotenv().ok();
 
@@ -86,16 +273,23 @@ async fn manual_login_cookie() -> Result<String, AppError> {
     client.close().await?;
     // Find session cookie
     if let Some(c) = cookies.iter().find(|c| c.name() == "_zenn_session") {
-        Ok(format!("_zenn_session={}", c.value()))
+        let expires = match c.expires() {
+            Some(Expiration::DateTime(dt)) => Some(dt.unix_timestamp() as u64),
+            _ => None,
+        };
+        Ok(SessionJar {
+            name: c.name().to_string(),
+            value: c.value().to_string(),
+            expires,
+        })
     } else {
         Err(AppError::BadSlug)
     }
 }
 
 /// Fetch scrap JSON, using optional cookie.
-async fn fetch_scrap(slug: &str, cookie: &str) -> Result<Scrap, AppError> {
+async fn fetch_scrap(client: &reqwest::Client, slug: &str, cookie: &str) -> Result<Scrap, AppError> {
     let url = format!("https://zenn.dev/api/scraps/{}/blob.json", slug);
-    let client = reqwest::Client::builder().build()?;
     let resp = client.get(&url)
         .header(reqwest::header::COOKIE, cookie)
         .send()
@@ -106,10 +300,137 @@ async fn fetch_scrap(slug: &str, cookie: &str) -> Result<Scrap, AppError> {
     Ok(resp.json().await?)
 }
 
+/// Regex matching Zenn image syntax: ![](url) or ![](url =200x)
+fn img_regex() -> Regex {
+    Regex::new(r"!\[\]\((?P<url>[^ )]+)(?: =(?P<width>\d+)x)?\)").unwrap()
+}
+
+/// Recursively collect every image URL referenced in `comments`, in document order.
+fn collect_image_urls(comments: &[Comment], urls: &mut Vec<String>) {
+    let img_re = img_regex();
+    for comment in comments {
+        for caps in img_re.captures_iter(&comment.body_markdown) {
+            urls.push(caps["url"].to_string());
+        }
+        if !comment.children.is_empty() {
+            collect_image_urls(&comment.children, urls);
+        }
+    }
+}
+
+/// Detect an image's MIME type from the HTTP `Content-Type`, falling back to magic-byte sniffing.
+fn detect_mime(content_type: Option<&str>, bytes: &[u8]) -> String {
+    if let Some(ct) = content_type {
+        if ct.starts_with("image/") {
+            return ct.to_string();
+        }
+    }
+    if bytes.starts_with(b"\x89PNG") {
+        "image/png".to_string()
+    } else if bytes.starts_with(b"\xFF\xD8") {
+        "image/jpeg".to_string()
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif".to_string()
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        "image/webp".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+/// File extension conventionally used for an image MIME type.
+fn mime_extension(mime: &str) -> &'static str {
+    match mime {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "bin",
+    }
+}
+
+/// Host of a URL, as used before the first `/` following `scheme://`.
+fn url_host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    Some(match rest.find('/') {
+        Some(pos) => &rest[..pos],
+        None => rest,
+    })
+}
+
+/// Whether `url` points at zenn.dev (or a subdomain), i.e. where the session cookie is valid.
+fn is_zenn_host(url: &str) -> bool {
+    matches!(url_host(url), Some(host) if host == "zenn.dev" || host.ends_with(".zenn.dev"))
+}
+
+/// Fetch a single image, attaching the session cookie only when the asset is actually on zenn.dev,
+/// returning its MIME type and bytes.
+async fn fetch_asset(client: &reqwest::Client, cookie: &str, url: &str) -> Result<(String, Vec<u8>), AppError> {
+    let mut req = client.get(url);
+    if is_zenn_host(url) {
+        req = req.header(reqwest::header::COOKIE, cookie);
+    }
+    let resp = req.send().await?;
+    let resp = resp.error_for_status()?;
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let bytes = resp.bytes().await?.to_vec();
+    let mime = detect_mime(content_type.as_deref(), &bytes);
+    Ok((mime, bytes))
+}
+
+/// Fetch every URL in `urls` concurrently, pairing each with its result.
+async fn fetch_all_assets(
+    client: &reqwest::Client,
+    cookie: &str,
+    urls: &[String],
+) -> Vec<(String, Result<(String, Vec<u8>), AppError>)> {
+    let fetches = urls.iter().map(|url| async move {
+        (url.clone(), fetch_asset(client, cookie, url).await)
+    });
+    join_all(fetches).await
+}
+
+/// Build `url -> data:<mime>;base64,...` substitutions for every successfully fetched asset.
+fn inline_substitutions(fetched: &[(String, Result<(String, Vec<u8>), AppError>)]) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for (url, result) in fetched {
+        match result {
+            Ok((mime, bytes)) => {
+                map.insert(url.clone(), format!("data:{};base64,{}", mime, BASE64.encode(bytes)));
+            }
+            Err(e) => eprintln!("Warning: failed to fetch image {}: {}", url, e),
+        }
+    }
+    map
+}
+
+/// Write each successfully fetched asset into `assets_dir`, returning `url -> relative path` substitutions.
+fn write_asset_files(
+    assets_dir: &Path,
+    assets_dir_name: &str,
+    fetched: &[(String, Result<(String, Vec<u8>), AppError>)],
+) -> Result<HashMap<String, String>, AppError> {
+    let mut map = HashMap::new();
+    for (i, (url, result)) in fetched.iter().enumerate() {
+        match result {
+            Ok((mime, bytes)) => {
+                let filename = format!("{:03}.{}", i, mime_extension(mime));
+                fs::write(assets_dir.join(&filename), bytes)?;
+                map.insert(url.clone(), format!("{}/{}", assets_dir_name, filename));
+            }
+            Err(e) => eprintln!("Warning: failed to fetch image {}: {}", url, e),
+        }
+    }
+    Ok(map)
+}
+
 /// Recursively render comments, converting Zenn image syntax to HTML and separating messages with lines.
-fn render_comments(comments: &[Comment], out: &mut String, skip_header: bool) {
-    // Regex to match Zenn image syntax: ![](url) or ![](url =200x)
-    let img_re = Regex::new(r"!\[\]\((?P<url>[^ )]+)(?: =(?P<width>\d+)x)?\)").unwrap();
+fn render_comments(comments: &[Comment], out: &mut String, skip_header: bool, assets: &HashMap<String, String>) {
+    let img_re = img_regex();
 
     for (i, comment) in comments.iter().enumerate() {
         // Optionally render header line for each comment
@@ -117,13 +438,14 @@ fn render_comments(comments: &[Comment], out: &mut String, skip_header: bool) {
             out.push_str(&format!("**{} ({})**\n\n", comment.author, comment.created_at));
         }
 
-        // Convert all image syntaxes in body_markdown
+        // Convert all image syntaxes in body_markdown, substituting embedded assets if present
         let processed = img_re.replace_all(&comment.body_markdown, |caps: &regex::Captures| {
             let url = &caps["url"];
+            let src = assets.get(url).map(|s| s.as_str()).unwrap_or(url);
             if let Some(w) = caps.name("width") {
-                format!("<img src=\"{}\" width=\"{}\">", url, w.as_str())
+                format!("<img src=\"{}\" width=\"{}\">", src, w.as_str())
             } else {
-                format!("<img src=\"{}\">", url)
+                format!("<img src=\"{}\">", src)
             }
         });
 
@@ -133,7 +455,7 @@ fn render_comments(comments: &[Comment], out: &mut String, skip_header: bool) {
 
         // Render child comments, passing the same skip_header flag
         if !comment.children.is_empty() {
-            render_comments(&comment.children, out, skip_header);
+            render_comments(&comment.children, out, skip_header, assets);
         }
         if !skip_header {
             // Insert horizontal rule between top-level comments
@@ -145,34 +467,356 @@ fn render_comments(comments: &[Comment], out: &mut String, skip_header: bool) {
 }
 
 /// Render entire scrap as Markdown.
-fn render_markdown(scrap: &Scrap, url: &str, skip_header: bool) -> String {
+fn render_markdown(scrap: &Scrap, url: &str, skip_header: bool, assets: &HashMap<String, String>) -> String {
     let mut out = String::new();
     out.push_str(&format!("# {}\n\n", scrap.title));
     out.push_str(&format!("Original: [{}]({})\n\n", url.replace("https://zenn.dev/", ""), url));
-    render_comments(&scrap.comments, &mut out, skip_header);
+    render_comments(&scrap.comments, &mut out, skip_header, assets);
     out
 }
 
+/// Parse a `--from-file` list: one URL/slug per line, trimmed, blank lines dropped.
+fn parse_input_list(contents: &str) -> Vec<String> {
+    contents.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect()
+}
+
+/// Tally a batch run's per-item outcomes (`true` = succeeded) into (succeeded, failed) counts.
+fn summarize_outcomes(outcomes: &[bool]) -> (usize, usize) {
+    let succeeded = outcomes.iter().filter(|&&ok| ok).count();
+    (succeeded, outcomes.len() - succeeded)
+}
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
     let args = Args::parse();
-    // Determine scrap slug
-    let slug = extract_slug(&args.url)?;
-    // Determine session cookie: CLI > ENV > manual login
+    let session_store = args
+        .session_store
+        .clone()
+        .map(PathBuf::from)
+        .unwrap_or_else(default_session_store_path);
+
+    if args.logout {
+        clear_session_jar(&session_store)?;
+        println!("Cleared saved session at {}", session_store.display());
+        return Ok(());
+    }
+
+    // Gather every scrap to fetch: positional args plus an optional --from-file list.
+    let mut inputs = args.url.clone();
+    if let Some(path) = args.from_file.clone() {
+        let contents = fs::read_to_string(&path)?;
+        inputs.extend(parse_input_list(&contents));
+    }
+    if inputs.is_empty() {
+        return Err(AppError::BadSlug);
+    }
+
+    // Determine session cookie once: CLI > --cookie-file > ENV > saved session jar > manual login.
+    // Matched against the real scrap API path so jar entries scoped below the root still apply.
     let cookie = if let Some(c) = args.cookie.clone() {
         c
+    } else if let Some(path) = args.cookie_file.clone() {
+        let jar = parse_cookie_jar(&path)?;
+        cookie_header_for_url(&jar, "https://zenn.dev/api/scraps/")
     } else if let Ok(envc) = env::var("ZENN_AUTH_COOKIE") {
         envc
+    } else if let Some(jar) = load_session_jar(&session_store) {
+        jar.cookie_header()
     } else {
-        manual_login_cookie().await?
+        let jar = manual_login_cookie().await?;
+        let cookie = jar.cookie_header();
+        save_session_jar(&session_store, &jar)?;
+        cookie
     };
-    
-    let scrap = fetch_scrap(&slug, &cookie).await?;
+
+    let client = reqwest::Client::builder().build()?;
+    // --output only makes sense when fetching a single scrap.
+    let single_output = if inputs.len() == 1 { args.output.clone() } else { None };
+
+    let mut outcomes = Vec::with_capacity(inputs.len());
+    for input in &inputs {
+        match process_scrap(
+            &client,
+            &cookie,
+            input,
+            single_output.as_deref(),
+            args.skip_header,
+            args.embed_assets,
+            args.inline,
+        )
+        .await
+        {
+            Ok(out) => {
+                println!("OK   {} -> {}", input, out);
+                outcomes.push(true);
+            }
+            Err(e) => {
+                eprintln!("FAIL {}: {}", input, e);
+                outcomes.push(false);
+            }
+        }
+    }
+
+    let (succeeded, failed) = summarize_outcomes(&outcomes);
+    if inputs.len() > 1 {
+        println!("{} succeeded, {} failed (of {})", succeeded, failed, inputs.len());
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Fetch one scrap and render it to Markdown, returning the path written.
+async fn process_scrap(
+    client: &reqwest::Client,
+    cookie: &str,
+    input: &str,
+    output: Option<&str>,
+    skip_header: bool,
+    embed_assets: bool,
+    inline: bool,
+) -> Result<String, AppError> {
+    let slug = extract_slug(input)?;
+    let scrap = fetch_scrap(client, &slug, cookie).await?;
     let title = scrap.title.clone();
-    let md = render_markdown(&scrap, &args.url, args.skip_header);
-    let out = args.output.clone().unwrap_or_else(|| format!("{}({}).md", title, slug));
+    let out = output.map(|s| s.to_string()).unwrap_or_else(|| format!("{}({}).md", title, slug));
+
+    let assets = if embed_assets {
+        let mut urls = Vec::new();
+        collect_image_urls(&scrap.comments, &mut urls);
+        urls.sort();
+        urls.dedup();
+        if urls.is_empty() {
+            HashMap::new()
+        } else {
+            let fetched = fetch_all_assets(client, cookie, &urls).await;
+            if inline {
+                inline_substitutions(&fetched)
+            } else {
+                let assets_dir_name = format!(
+                    "{}_assets",
+                    Path::new(&out).file_stem().and_then(|s| s.to_str()).unwrap_or("scrap")
+                );
+                let assets_dir = Path::new(&out)
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .join(&assets_dir_name);
+                fs::create_dir_all(&assets_dir)?;
+                write_asset_files(&assets_dir, &assets_dir_name, &fetched)?
+            }
+        }
+    } else {
+        HashMap::new()
+    };
+
+    let md = render_markdown(&scrap, input, skip_header, &assets);
     fs::write(&out, md)?;
-    println!("Saved Markdown to {}", out);
-    Ok(())
+    Ok(out)
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_list_trims_and_drops_blank_lines() {
+        let contents = "  https://zenn.dev/a/scraps/one  \n\n\tfoo-slug\t\n   \nbar-slug\n";
+        assert_eq!(
+            parse_input_list(contents),
+            vec!["https://zenn.dev/a/scraps/one", "foo-slug", "bar-slug"]
+        );
+    }
+
+    #[test]
+    fn parse_input_list_of_empty_contents_is_empty() {
+        assert!(parse_input_list("\n\n   \n").is_empty());
+    }
+
+    #[test]
+    fn summarize_outcomes_counts_successes_and_failures() {
+        assert_eq!(summarize_outcomes(&[true, false, true, true, false]), (3, 2));
+    }
+
+    #[test]
+    fn summarize_outcomes_handles_all_success_and_empty() {
+        assert_eq!(summarize_outcomes(&[true, true]), (2, 0));
+        assert_eq!(summarize_outcomes(&[]), (0, 0));
+    }
+}
+
+#[cfg(test)]
+mod session_jar_tests {
+    use super::*;
+
+    #[test]
+    fn no_expiry_never_expires() {
+        let jar = SessionJar {
+            name: "_zenn_session".to_string(),
+            value: "v".to_string(),
+            expires: None,
+        };
+        assert!(!jar.is_expired());
+    }
+
+    #[test]
+    fn past_expiry_is_expired() {
+        let jar = SessionJar {
+            name: "_zenn_session".to_string(),
+            value: "v".to_string(),
+            expires: Some(1),
+        };
+        assert!(jar.is_expired());
+    }
+
+    #[test]
+    fn far_future_expiry_is_not_expired() {
+        let jar = SessionJar {
+            name: "_zenn_session".to_string(),
+            value: "v".to_string(),
+            expires: Some(32_503_680_000), // year 3000
+        };
+        assert!(!jar.is_expired());
+    }
+
+    #[test]
+    fn cookie_header_is_name_value() {
+        let jar = SessionJar {
+            name: "_zenn_session".to_string(),
+            value: "abc123".to_string(),
+            expires: None,
+        };
+        assert_eq!(jar.cookie_header(), "_zenn_session=abc123");
+    }
+}
+
+#[cfg(test)]
+mod asset_tests {
+    use super::*;
+
+    #[test]
+    fn detect_mime_prefers_content_type() {
+        assert_eq!(detect_mime(Some("image/png"), b"not actually png"), "image/png");
+    }
+
+    #[test]
+    fn detect_mime_falls_back_to_magic_bytes() {
+        assert_eq!(detect_mime(None, b"\x89PNG\r\n\x1a\n"), "image/png");
+        assert_eq!(detect_mime(None, b"\xFF\xD8\xFF\xE0"), "image/jpeg");
+        assert_eq!(detect_mime(None, b"GIF89a"), "image/gif");
+        assert_eq!(detect_mime(None, b"RIFF....WEBP"), "image/webp");
+        assert_eq!(detect_mime(None, b"whatever"), "application/octet-stream");
+    }
+
+    #[test]
+    fn detect_mime_ignores_non_image_content_type() {
+        assert_eq!(detect_mime(Some("text/html"), b"\x89PNG"), "image/png");
+    }
+
+    #[test]
+    fn mime_extension_covers_known_types() {
+        assert_eq!(mime_extension("image/png"), "png");
+        assert_eq!(mime_extension("image/jpeg"), "jpg");
+        assert_eq!(mime_extension("image/gif"), "gif");
+        assert_eq!(mime_extension("image/webp"), "webp");
+        assert_eq!(mime_extension("application/octet-stream"), "bin");
+    }
+
+    #[test]
+    fn is_zenn_host_matches_domain_and_subdomains_only() {
+        assert!(is_zenn_host("https://zenn.dev/api/scraps/foo"));
+        assert!(is_zenn_host("https://storage.zenn.dev/images/foo.png"));
+        assert!(!is_zenn_host("https://storage.googleapis.com/zenn-user-upload/foo.png"));
+        assert!(!is_zenn_host("not-a-url"));
+    }
+}
+
+#[cfg(test)]
+mod cookie_jar_tests {
+    use super::*;
+
+    #[test]
+    fn parses_httponly_and_comment_lines() {
+        let jar = "# Netscape HTTP Cookie File\n\
+                   # this is a comment\n\
+                   \n\
+                   zenn.dev\tFALSE\t/\tTRUE\t0\tsession\tabc\n\
+                   #HttpOnly_zenn.dev\tTRUE\t/api\tTRUE\t9999999999\t_zenn_session\txyz\n";
+        let cookies = parse_cookie_jar_str(jar).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name, "session");
+        assert_eq!(cookies[1].name, "_zenn_session");
+        assert!(cookies[1].include_subdomains);
+        assert_eq!(cookies[1].path, "/api");
+    }
+
+    #[test]
+    fn strips_leading_dot_from_domain() {
+        // Real-world exports write a leading dot on the domain when it applies to subdomains.
+        let jar = ".zenn.dev\tTRUE\t/\tTRUE\t0\t_zenn_session\txyz\n";
+        let cookies = parse_cookie_jar_str(jar).unwrap();
+        assert_eq!(cookies[0].domain, "zenn.dev");
+        assert!(cookies[0].matches_url("https://zenn.dev/api/scraps/foo"));
+        assert!(cookies[0].matches_url("https://sub.zenn.dev/"));
+    }
+
+    #[test]
+    fn rejects_malformed_line() {
+        let err = parse_cookie_jar_str("zenn.dev\tFALSE\t/\n").unwrap_err();
+        assert!(matches!(err, AppError::BadCookieJar(_)));
+    }
+
+    #[test]
+    fn session_cookie_with_zero_expiry_never_expires() {
+        let c = NetscapeCookie {
+            domain: "zenn.dev".to_string(),
+            include_subdomains: false,
+            path: "/".to_string(),
+            secure: false,
+            expires: 0,
+            name: "a".to_string(),
+            value: "b".to_string(),
+        };
+        assert!(!c.is_expired());
+    }
+
+    #[test]
+    fn matches_url_honors_include_subdomains_secure_and_path() {
+        let root = NetscapeCookie {
+            domain: "zenn.dev".to_string(),
+            include_subdomains: true,
+            path: "/".to_string(),
+            secure: true,
+            expires: 0,
+            name: "root".to_string(),
+            value: "v".to_string(),
+        };
+        assert!(root.matches_url("https://zenn.dev/api/scraps/foo"));
+        assert!(root.matches_url("https://www.zenn.dev/"));
+        assert!(!root.matches_url("http://zenn.dev/"), "secure cookie must not go over http");
+
+        let scoped = NetscapeCookie {
+            domain: "zenn.dev".to_string(),
+            include_subdomains: false,
+            path: "/api".to_string(),
+            secure: false,
+            expires: 0,
+            name: "scoped".to_string(),
+            value: "v".to_string(),
+        };
+        assert!(scoped.matches_url("https://zenn.dev/api/scraps/foo"));
+        assert!(!scoped.matches_url("https://zenn.dev/other"));
+        assert!(!scoped.matches_url("https://sub.zenn.dev/api/"), "no subdomain match when not opted in");
+    }
+
+    #[test]
+    fn cookie_header_for_url_joins_only_matching_non_expired_cookies() {
+        let cookies = parse_cookie_jar_str(
+            "zenn.dev\tFALSE\t/api\tFALSE\t0\tlive\tyes\n\
+             zenn.dev\tFALSE\t/\tFALSE\t1\texpired\tno\n",
+        )
+        .unwrap();
+        let header = cookie_header_for_url(&cookies, "https://zenn.dev/api/scraps/foo");
+        assert_eq!(header, "live=yes");
+    }
 }